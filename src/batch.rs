@@ -0,0 +1,217 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+extern crate bellman;
+extern crate pairing;
+extern crate rand;
+
+use std::time::Instant;
+
+// For randomness (during paramgen and proof generation)
+use self::rand::{thread_rng, Rng};
+
+// Bring in some tools for using pairing-friendly curves
+use self::pairing::{
+    Engine,
+    Field,
+    PrimeField,
+    CurveAffine,
+    CurveProjective
+};
+
+// We're going to use the BLS12-381 pairing-friendly elliptic curve.
+use self::pairing::bls12_381::{
+    Bls12,
+    Fr
+};
+
+// We'll use these interfaces to construct our circuit.
+use self::bellman::{
+    Circuit,
+    SynthesisError
+};
+
+// We're going to use the Groth16 proving system.
+use self::bellman::groth16::{
+    Proof,
+    Parameters,
+    VerifyingKey,
+    generate_random_parameters,
+    prepare_verifying_key,
+    create_random_proof,
+    verify_proof,
+};
+
+use circle::CircleDemo;
+
+// Generate one Groth16 proof per circuit sharing the same parameters. This is
+// just a convenience over `create_random_proof` so callers hand the batch
+// verifier a slice of proofs in one go.
+pub fn create_random_proof_batch<E, C, R>(
+    circuits: Vec<C>,
+    params: &Parameters<E>,
+    rng: &mut R
+) -> Result<Vec<Proof<E>>, SynthesisError>
+    where E: Engine, C: Circuit<E>, R: Rng
+{
+    circuits.into_iter()
+        .map(|c| create_random_proof(c, params, rng))
+        .collect()
+}
+
+// Amortized batch verification.
+//
+// Verifying a single Groth16 proof checks
+//
+//     e(A, B) * e(acc, -gamma) * e(C, -delta) == e(alpha, beta)
+//
+// where `acc = IC_0 + sum_i input_i * IC_{i+1}`. Raising the whole identity to
+// a random scalar `r_j` keeps it true, so picking independent `r_1..r_N` and
+// multiplying the checks together gives
+//
+//     prod_j e(r_j A_j, B_j) * e(sum_j r_j acc_j, -gamma)
+//         * e(sum_j r_j C_j, -delta) == e(alpha, beta)^(sum_j r_j)
+//
+// The `-gamma` and `-delta` terms share their G2 element across all proofs, so
+// their G1 parts collapse into a single sum. The result is one multi-pairing
+// of `N + 2` terms instead of `3N`, and a forged proof survives the random
+// combination only with negligible probability.
+pub fn verify_proofs_batch<E, R>(
+    vk: &VerifyingKey<E>,
+    proofs: &[&Proof<E>],
+    public_inputs: &[&[E::Fr]],
+    rng: &mut R
+) -> Result<bool, SynthesisError>
+    where E: Engine, R: Rng
+{
+    assert_eq!(proofs.len(), public_inputs.len());
+
+    // -gamma and -delta in G2, prepared once and shared by every proof.
+    let mut neg_gamma = vk.gamma_g2;
+    neg_gamma.negate();
+    let neg_gamma = neg_gamma.prepare();
+
+    let mut neg_delta = vk.delta_g2;
+    neg_delta.negate();
+    let neg_delta = neg_delta.prepare();
+
+    // The random-linear-combination accumulators.
+    let mut acc_sum = <E::G1 as CurveProjective>::zero();
+    let mut c_sum = <E::G1 as CurveProjective>::zero();
+    let mut r_total = E::Fr::zero();
+
+    // Prepared (r_j A_j, B_j) pairs, kept alive for the multi-pairing below.
+    let mut a_prepared = Vec::with_capacity(proofs.len());
+    let mut b_prepared = Vec::with_capacity(proofs.len());
+
+    for (proof, inputs) in proofs.iter().zip(public_inputs.iter()) {
+        if (inputs.len() + 1) != vk.ic.len() {
+            return Err(SynthesisError::MalformedVerifyingKey);
+        }
+
+        let r: E::Fr = rng.gen();
+
+        // r * A_j paired with B_j.
+        let mut a = proof.a.into_projective();
+        a.mul_assign(r.into_repr());
+        a_prepared.push(a.into_affine().prepare());
+        b_prepared.push(proof.b.prepare());
+
+        // acc_j = IC_0 + sum_i input_i * IC_{i+1}
+        let mut acc = vk.ic[0].into_projective();
+        for (input, ic) in inputs.iter().zip(vk.ic.iter().skip(1)) {
+            acc.add_assign(&ic.mul(input.into_repr()));
+        }
+        acc.mul_assign(r.into_repr());
+        acc_sum.add_assign(&acc);
+
+        // r * C_j
+        let mut c = proof.c.into_projective();
+        c.mul_assign(r.into_repr());
+        c_sum.add_assign(&c);
+
+        r_total.add_assign(&r);
+    }
+
+    let acc_prepared = acc_sum.into_affine().prepare();
+    let c_prepared = c_sum.into_affine().prepare();
+
+    let mut terms = Vec::with_capacity(proofs.len() + 2);
+    for i in 0..proofs.len() {
+        terms.push((&a_prepared[i], &b_prepared[i]));
+    }
+    terms.push((&acc_prepared, &neg_gamma));
+    terms.push((&c_prepared, &neg_delta));
+
+    let lhs = E::final_exponentiation(&E::miller_loop(terms.iter()))
+        .ok_or(SynthesisError::UnexpectedIdentity)?;
+
+    // e(alpha, beta) raised to the sum of the random scalars.
+    let mut rhs = E::pairing(vk.alpha_g1, vk.beta_g2);
+    rhs = rhs.pow(r_total.into_repr());
+
+    Ok(lhs == rhs)
+}
+
+#[test]
+fn test_batch_proof(){
+    // This may not be cryptographically safe, use
+    // `OsRng` (for example) in production software.
+    let rng = &mut thread_rng();
+
+    println!("SETUP: Creating parameters...");
+
+    let params = {
+        let c = CircleDemo::<Bls12> {
+            x: None,
+            y: None,
+            r: None,
+        };
+
+        generate_random_parameters(c, rng).unwrap()
+    };
+
+    let pvk = prepare_verifying_key(&params.vk);
+
+    // A handful of (3, 4, 5) and (4, 3, 5) points on the radius-5 circle.
+    let public_radius = Fr::from_str("5").unwrap();
+    let witnesses = [
+        (Fr::from_str("4"), Fr::from_str("3")),
+        (Fr::from_str("3"), Fr::from_str("4")),
+        (Fr::from_str("4"), Fr::from_str("3")),
+        (Fr::from_str("3"), Fr::from_str("4")),
+    ];
+
+    let circuits = witnesses.iter().map(|&(x, y)| CircleDemo::<Bls12> {
+        x: x,
+        y: y,
+        r: Some(public_radius),
+    }).collect::<Vec<_>>();
+
+    println!("Alice: Creating {} proofs...", circuits.len());
+    let proofs = create_random_proof_batch(circuits, &params, rng).unwrap();
+
+    let proof_refs = proofs.iter().collect::<Vec<_>>();
+    let inputs = vec![&[public_radius][..]; proofs.len()];
+
+    // Verify one-by-one for the baseline result and timing.
+    let one_by_one = Instant::now();
+    for proof in &proofs {
+        assert!(verify_proof(&pvk, proof, &[public_radius]).unwrap());
+    }
+    let one_by_one = one_by_one.elapsed();
+
+    // Verify the whole batch with a single multi-pairing.
+    let batched = Instant::now();
+    let ok = verify_proofs_batch(&params.vk, &proof_refs, &inputs, rng).unwrap();
+    let batched = batched.elapsed();
+
+    // The batched verdict must agree with verifying each proof individually.
+    assert!(ok);
+
+    println!(
+        "Bob: one-by-one {:?} vs batched {:?} for {} proofs",
+        one_by_one,
+        batched,
+        proofs.len()
+    );
+}
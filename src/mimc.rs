@@ -0,0 +1,231 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+extern crate bellman;
+extern crate pairing;
+extern crate rand;
+
+// For randomness (during paramgen and proof generation)
+use self::rand::{thread_rng, Rng};
+
+// Bring in some tools for using pairing-friendly curves
+use self::pairing::{
+    Engine,
+    Field,
+    PrimeField
+};
+
+// We're going to use the BLS12-381 pairing-friendly elliptic curve.
+use self::pairing::bls12_381::{
+    Bls12,
+    Fr
+};
+
+// We'll use these interfaces to construct our circuit.
+use self::bellman::{
+    Circuit,
+    ConstraintSystem,
+    SynthesisError
+};
+
+// We're going to use the Groth16 proving system.
+use self::bellman::groth16::{
+    Proof,
+    generate_random_parameters,
+    prepare_verifying_key,
+    create_random_proof,
+    verify_proof,
+};
+
+// The number of rounds in the LongsightF322p3 MiMC permutation. Each round
+// consumes one constant, so the circuit carries exactly this many of them.
+const MIMC_ROUNDS: usize = 322;
+
+// LongsightF322p3 evaluated out of circuit. This mirrors the in-circuit
+// construction exactly so callers can compute the expected image (the hash of
+// the preimage) to feed in as the public input.
+//
+//     xL, xR := xR + (xL + C_i)^3, xL
+//
+fn mimc<E: Engine>(
+    mut xl: E::Fr,
+    mut xr: E::Fr,
+    constants: &[E::Fr]
+) -> E::Fr
+{
+    assert_eq!(constants.len(), MIMC_ROUNDS);
+
+    for i in 0..MIMC_ROUNDS {
+        // tmp = xL + C_i
+        let mut tmp = xl;
+        tmp.add_assign(&constants[i]);
+        // cube = tmp^3
+        let mut cube = tmp;
+        cube.square();
+        cube.mul_assign(&tmp);
+        // new xL = cube + xR, new xR = old xL
+        cube.add_assign(&xr);
+        xr = xl;
+        xl = cube;
+    }
+
+    xl
+}
+
+// Proving that I know a MiMC preimage (xl, xr) hashing to a public image.
+// Packaged as a reusable gadget: the round constants are sampled once and
+// carried in the struct so the prover and verifier agree on the permutation.
+pub struct MiMCDemo<'a, E: Engine> {
+    pub xl: Option<E::Fr>,
+    pub xr: Option<E::Fr>,
+    pub constants: &'a [E::Fr],
+}
+
+impl <'a, E: Engine> Circuit<E> for MiMCDemo<'a, E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        assert_eq!(self.constants.len(), MIMC_ROUNDS);
+
+        // Allocate the two halves of the preimage as private witnesses.
+        let mut xl_val = self.xl;
+        let mut xl = cs.alloc(|| "preimage xl", || {
+            xl_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let mut xr_val = self.xr;
+        let mut xr = cs.alloc(|| "preimage xr", || {
+            xr_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        for i in 0..MIMC_ROUNDS {
+            // Keep each round in its own namespace so the constraint names stay
+            // unique across the 322 iterations.
+            let cs = &mut cs.namespace(|| format!("round {}", i));
+
+            let ci = self.constants[i];
+
+            // tmp = xl + C_i. The constant add folds into the linear
+            // combination, so there is no allocation or constraint for it.
+            let tmp_val = xl_val.map(|mut e| {
+                e.add_assign(&ci);
+                e
+            });
+
+            // Allocate: tmp * tmp = tmp_sq
+            let tmp_sq_val = tmp_val.map(|mut e| {
+                e.square();
+                e
+            });
+            let tmp_sq = cs.alloc(|| "tmp_sq", || {
+                tmp_sq_val.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            // Enforce: (xl + C_i) * (xl + C_i) = tmp_sq
+            cs.enforce(
+                || "tmp_sq",
+                |lc| lc + xl + (ci, CS::one()),
+                |lc| lc + xl + (ci, CS::one()),
+                |lc| lc + tmp_sq
+            );
+
+            // Allocate: tmp_sq * tmp = cube
+            let cube_val = tmp_sq_val.and_then(|mut e| tmp_val.map(|t| {
+                e.mul_assign(&t);
+                e
+            }));
+            let cube = cs.alloc(|| "cube", || {
+                cube_val.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            // Enforce: tmp_sq * (xl + C_i) = cube
+            cs.enforce(
+                || "cube",
+                |lc| lc + tmp_sq,
+                |lc| lc + xl + (ci, CS::one()),
+                |lc| lc + cube
+            );
+
+            // new xl = cube + xr. On the final round expose it as the public
+            // image via alloc_input and enforce equality with cube + xr.
+            let new_xl_val = cube_val.and_then(|mut e| xr_val.map(|r| {
+                e.add_assign(&r);
+                e
+            }));
+            let new_xl = if i == MIMC_ROUNDS - 1 {
+                cs.alloc_input(|| "image", || {
+                    new_xl_val.ok_or(SynthesisError::AssignmentMissing)
+                })?
+            } else {
+                cs.alloc(|| "new_xl", || {
+                    new_xl_val.ok_or(SynthesisError::AssignmentMissing)
+                })?
+            };
+            // Enforce: (cube + xr) * 1 = new_xl
+            cs.enforce(
+                || "new_xl",
+                |lc| lc + cube + xr,
+                |lc| lc + CS::one(),
+                |lc| lc + new_xl
+            );
+
+            // Rotate: new xr is the old xl.
+            xr = xl;
+            xr_val = xl_val;
+            xl = new_xl;
+            xl_val = new_xl_val;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_mimc_proof(){
+    // This may not be cryptographically safe, use
+    // `OsRng` (for example) in production software.
+    let rng = &mut thread_rng();
+
+    // Sample the round constants once; both parameter generation and proving
+    // share the same permutation.
+    let constants = (0..MIMC_ROUNDS).map(|_| rng.gen()).collect::<Vec<_>>();
+
+    println!("SETUP: Creating parameters...");
+
+    // Create parameters for our circuit
+    let params = {
+        let c = MiMCDemo::<Bls12> {
+            xl: None,
+            xr: None,
+            constants: &constants,
+        };
+
+        generate_random_parameters(c, rng).unwrap()
+    };
+
+    // Prepare the verification key (for proof verification)
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let xl = rng.gen();
+    let xr = rng.gen();
+    let image = mimc::<Bls12>(xl, xr, &constants);
+
+    println!("Alice: Creating proofs...");
+
+    // Create an instance of circuit
+    let c = MiMCDemo::<Bls12> {
+        xl: Some(xl),
+        xr: Some(xr),
+        constants: &constants,
+    };
+
+    // Create a groth16 proof with our parameters.
+    let proof = create_random_proof(c, &params, rng).unwrap();
+
+    println!("Bob: Verifying...");
+
+    assert!(verify_proof(
+        &pvk,
+        &proof,
+        &[image]
+    ).unwrap());
+}
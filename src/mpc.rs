@@ -0,0 +1,112 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+extern crate bellman;
+extern crate pairing;
+extern crate phase2;
+extern crate rand;
+
+// For randomness (during paramgen and proof generation)
+use self::rand::{thread_rng, Rng};
+
+// Bring in some tools for using pairing-friendly curves
+use self::pairing::{
+    Engine,
+    Field,
+    PrimeField
+};
+
+// We're going to use the BLS12-381 pairing-friendly elliptic curve.
+use self::pairing::bls12_381::{
+    Bls12,
+    Fr
+};
+
+// We'll use these interfaces to construct our circuit.
+use self::bellman::{
+    Circuit,
+    SynthesisError
+};
+
+// We're going to use the Groth16 proving system.
+use self::bellman::groth16::{
+    prepare_verifying_key,
+    create_random_proof,
+    verify_proof,
+};
+
+// The phase2 crate turns a phase-1 powers-of-tau transcript into circuit
+// specific parameters through a multi-party ceremony.
+use self::phase2::{
+    MPCParameters,
+    contains_contribution,
+};
+
+use circle::CircleDemo;
+
+// A fresh, unassigned instance of the circuit. Parameter generation and
+// verification only depend on the circuit's shape, so every stage of the
+// ceremony hands the machinery one of these.
+fn blank_circuit() -> CircleDemo<Bls12> {
+    CircleDemo {
+        x: None,
+        y: None,
+        r: None,
+    }
+}
+
+#[test]
+fn test_mpc_ceremony(){
+    // This may not be cryptographically safe, use
+    // `OsRng` (for example) in production software.
+    let rng = &mut thread_rng();
+
+    println!("SETUP: Seeding MPC parameters from the phase-1 transcript...");
+
+    // (1) Derive the initial parameters for the circle circuit from the
+    // phase-1 powers-of-tau transcript. At this point no one has contributed
+    // randomness yet, so the parameters are not safe to use.
+    let mut params = MPCParameters::new(blank_circuit()).unwrap();
+
+    // The running transcript of contribution hashes. Each participant appends
+    // one, and anyone can check a given contribution is part of the chain.
+    let mut transcript = Vec::new();
+
+    // (2)/(3) Three participants each re-randomize the parameters and publish
+    // their public-key attestation. After every contribution we re-verify the
+    // whole chain so a later participant can catch an earlier invalid step.
+    for i in 0..3 {
+        println!("Participant {}: Contributing randomness...", i + 1);
+        let hash = params.contribute(rng);
+        transcript.push(hash);
+
+        let contributions = params.verify(blank_circuit())
+            .expect("the contribution chain should remain valid");
+
+        // Every attestation recorded so far must still appear in the chain.
+        for hash in &transcript {
+            assert!(contains_contribution(&contributions, hash));
+        }
+    }
+
+    // (4) The re-randomized parameters are now safe: extract the plain
+    // Parameters for the ordinary prover/verifier.
+    let params = params.get_params().clone();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let public_radius = Fr::from_str("5").unwrap();
+
+    println!("Alice: Creating proof against the ceremony key...");
+    let c = CircleDemo::<Bls12> {
+        x: Fr::from_str("4"),
+        y: Fr::from_str("3"),
+        r: Some(public_radius),
+    };
+    let proof = create_random_proof(c, &params, rng).unwrap();
+
+    println!("Bob: Verifying...");
+    assert!(verify_proof(
+        &pvk,
+        &proof,
+        &[public_radius]
+    ).unwrap());
+}
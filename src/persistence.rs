@@ -0,0 +1,111 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+extern crate bellman;
+extern crate pairing;
+extern crate rand;
+
+use std::fs::File;
+use std::env;
+
+// For randomness (during paramgen and proof generation)
+use self::rand::{thread_rng, Rng};
+
+// Bring in some tools for using pairing-friendly curves
+use self::pairing::{
+    Engine,
+    Field,
+    PrimeField
+};
+
+// We're going to use the BLS12-381 pairing-friendly elliptic curve.
+use self::pairing::bls12_381::{
+    Bls12,
+    Fr
+};
+
+// We'll use these interfaces to construct our circuit.
+use self::bellman::{
+    Circuit,
+    SynthesisError
+};
+
+// We're going to use the Groth16 proving system.
+use self::bellman::groth16::{
+    Proof,
+    Parameters,
+    VerifyingKey,
+    generate_random_parameters,
+    prepare_verifying_key,
+    create_random_proof,
+    verify_proof,
+};
+
+use circle::CircleDemo;
+
+#[test]
+fn test_serialize_proof(){
+    // This may not be cryptographically safe, use
+    // `OsRng` (for example) in production software.
+    let rng = &mut thread_rng();
+
+    // Scratch files standing in for the prover's and verifier's disks.
+    let dir = env::temp_dir();
+    let params_path = dir.join("circle_params.bin");
+    let vk_path = dir.join("circle_vk.bin");
+    let proof_path = dir.join("circle_proof.bin");
+
+    // ---- Trusted setup: generate parameters once and persist them. ----
+    println!("SETUP: Creating and saving parameters...");
+    {
+        let c = CircleDemo::<Bls12> {
+            x: None,
+            y: None,
+            r: None,
+        };
+        let params = generate_random_parameters::<Bls12, _, _>(c, rng).unwrap();
+
+        let mut f = File::create(&params_path).unwrap();
+        params.write(&mut f).unwrap();
+
+        // The verifier only ever needs the verifying key, so write it out on
+        // its own as well.
+        let mut f = File::create(&vk_path).unwrap();
+        params.vk.write(&mut f).unwrap();
+    }
+
+    // ---- Prover: load the full parameters, prove, and persist the proof. ----
+    let public_radius = Fr::from_str("5").unwrap();
+    println!("Alice: Loading parameters and creating a proof...");
+    {
+        let mut f = File::open(&params_path).unwrap();
+        let params = Parameters::<Bls12>::read(&mut f, false).unwrap();
+
+        let c = CircleDemo::<Bls12> {
+            x: Fr::from_str("4"),
+            y: Fr::from_str("3"),
+            r: Some(public_radius),
+        };
+        let proof = create_random_proof(c, &params, rng).unwrap();
+
+        let mut f = File::create(&proof_path).unwrap();
+        proof.write(&mut f).unwrap();
+    }
+
+    // ---- Verifier: load only the verifying key and the proof. ----
+    // This side never sees the prover's memory or the full parameters.
+    println!("Bob: Loading the verifying key and proof...");
+    {
+        let mut f = File::open(&vk_path).unwrap();
+        let vk = VerifyingKey::<Bls12>::read(&mut f).unwrap();
+        let pvk = prepare_verifying_key(&vk);
+
+        let mut f = File::open(&proof_path).unwrap();
+        let proof = Proof::<Bls12>::read(&mut f).unwrap();
+
+        assert!(verify_proof(
+            &pvk,
+            &proof,
+            &[public_radius]
+        ).unwrap());
+    }
+}
@@ -0,0 +1,256 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+extern crate bellman;
+extern crate pairing;
+extern crate rand;
+extern crate sapling_crypto;
+
+// For randomness (during paramgen and proof generation)
+use self::rand::{thread_rng, Rng};
+
+// Bring in some tools for using pairing-friendly curves
+use self::pairing::{
+    Engine,
+    Field,
+    PrimeField
+};
+
+// We're going to use the BLS12-381 pairing-friendly elliptic curve.
+use self::pairing::bls12_381::{
+    Bls12,
+    Fr
+};
+
+// We'll use these interfaces to construct our circuit.
+use self::bellman::{
+    Circuit,
+    ConstraintSystem,
+    SynthesisError,
+    Variable
+};
+
+// We're going to use the Groth16 proving system.
+use self::bellman::groth16::{
+    Proof,
+    generate_random_parameters,
+    prepare_verifying_key,
+    create_random_proof,
+    verify_proof,
+};
+
+// The low-level `n` bits of a field element, little-endian.
+fn le_bits<E: Engine>(value: E::Fr, n: usize) -> Vec<bool> {
+    let repr = value.into_repr();
+    (0..n).map(|i| {
+        (repr.as_ref()[i / 64] >> (i % 64)) & 1 == 1
+    }).collect()
+}
+
+// Range-check gadget: prove `0 <= value < 2^n`.
+//
+// Allocate `n` boolean variables, enforce each is boolean with `b * (1 - b) =
+// 0`, and enforce the weighted sum `sum_i b_i * 2^i` equals `value` with a
+// single linear constraint. A value that does not fit in `n` bits cannot
+// satisfy that sum, so the circuit is unsatisfiable.
+fn range_check<E, CS>(
+    mut cs: CS,
+    value: Variable,
+    value_val: Option<E::Fr>,
+    n: usize
+) -> Result<(), SynthesisError>
+    where E: Engine, CS: ConstraintSystem<E>
+{
+    let bit_vals = value_val.map(|v| le_bits::<E>(v, n));
+
+    let mut bits = Vec::with_capacity(n);
+    for i in 0..n {
+        let bit_val = bit_vals.as_ref().map(|b| {
+            if b[i] { E::Fr::one() } else { E::Fr::zero() }
+        });
+        let bit = cs.alloc(|| format!("bit {}", i), || {
+            bit_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        // Enforce: b * (1 - b) = 0
+        cs.enforce(
+            || format!("bit {} is boolean", i),
+            |lc| lc + bit,
+            |lc| lc + CS::one() - bit,
+            |lc| lc
+        );
+
+        bits.push(bit);
+    }
+
+    // Enforce: (sum_i b_i * 2^i) * 1 = value
+    cs.enforce(
+        || "weighted sum",
+        |mut lc| {
+            let mut coeff = E::Fr::one();
+            for bit in &bits {
+                lc = lc + (coeff, *bit);
+                coeff.double();
+            }
+            lc
+        },
+        |lc| lc + CS::one(),
+        |lc| lc + value
+    );
+
+    Ok(())
+}
+
+// Proving that I know a point on the circle whose coordinates additionally lie
+// inside the box `0 <= x < 2^n` and `0 <= y < 2^n`. The box membership is
+// something plain R1CS equality cannot express, so it leans on the range-check
+// gadget above.
+pub struct RangeCircleDemo<E: Engine> {
+    pub x: Option<E::Fr>,
+    pub y: Option<E::Fr>,
+    pub r: Option<E::Fr>,
+    pub n: usize,
+}
+
+impl <E: Engine> Circuit<E> for RangeCircleDemo<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        // Allocate x and constrain it on the circle.
+        let x_val = self.x;
+        let x = cs.alloc(|| "x", || {
+            x_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let x_square_val = x_val.map(|mut e| {
+            e.square();
+            e
+        });
+        let x_square = cs.alloc(|| "x_square", || {
+            x_square_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        cs.enforce(
+            || "x_square",
+            |lc| lc + x,
+            |lc| lc + x,
+            |lc| lc + x_square
+        );
+
+        // Allocate y and constrain it on the circle.
+        let y_val = self.y;
+        let y = cs.alloc(|| "y", || {
+            y_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let y_square_val = y_val.map(|mut e| {
+            e.square();
+            e
+        });
+        let y_square = cs.alloc(|| "y_square", || {
+            y_square_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        cs.enforce(
+            || "y_square",
+            |lc| lc + y,
+            |lc| lc + y,
+            |lc| lc + y_square
+        );
+
+        // Allocating r (a public input) uses alloc_input
+        let r = cs.alloc_input(|| "r", || {
+            self.r.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let r_square_val = self.r.map(|mut e| {
+            e.square();
+            e
+        });
+        let r_square = cs.alloc(|| "r_square", || {
+            r_square_val.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        cs.enforce(
+            || "r_square",
+            |lc| lc + r,
+            |lc| lc + r,
+            |lc| lc + r_square
+        );
+
+        // (x_square + y_square) * 1 = r_square
+        cs.enforce(
+            || "circle",
+            |lc| lc + x_square + y_square,
+            |lc| lc + CS::one(),
+            |lc| lc + r_square
+        );
+
+        // And additionally that both coordinates fit inside the box.
+        range_check(cs.namespace(|| "x in range"), x, x_val, self.n)?;
+        range_check(cs.namespace(|| "y in range"), y, y_val, self.n)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_range_circle_proof(){
+    // This may not be cryptographically safe, use
+    // `OsRng` (for example) in production software.
+    let rng = &mut thread_rng();
+
+    println!("SETUP: Creating parameters...");
+
+    let params = {
+        let c = RangeCircleDemo::<Bls12> {
+            x: None,
+            y: None,
+            r: None,
+            n: 8,
+        };
+
+        generate_random_parameters(c, rng).unwrap()
+    };
+
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let public_radius = Fr::from_str("5").unwrap();
+
+    println!("Alice: Creating proof for an in-range point...");
+    let c = RangeCircleDemo::<Bls12> {
+        x: Fr::from_str("4"),
+        y: Fr::from_str("3"),
+        r: Some(public_radius),
+        n: 8,
+    };
+    let proof = create_random_proof(c, &params, rng).unwrap();
+
+    println!("Bob: Verifying...");
+    assert!(verify_proof(
+        &pvk,
+        &proof,
+        &[public_radius]
+    ).unwrap());
+}
+
+#[test]
+fn test_range_circle_out_of_range(){
+    use self::sapling_crypto::circuit::test::TestConstraintSystem;
+
+    // (7, 24) sits exactly on the radius-25 circle, so the circle constraints
+    // are all satisfied. With n = 4 the box is `0 <= _ < 16`: x = 7 fits but
+    // y = 24 needs 5 bits, so the only unsatisfied constraint is the range
+    // check on y. Synthesis itself still succeeds (it returns Ok) — the
+    // witness simply fails to satisfy the range gadget's constraints.
+    let c = RangeCircleDemo::<Bls12> {
+        x: Fr::from_str("7"),
+        y: Fr::from_str("24"),
+        r: Fr::from_str("25"),
+        n: 4,
+    };
+
+    let mut cs = TestConstraintSystem::<Bls12>::new();
+    c.synthesize(&mut cs).unwrap();
+
+    assert!(!cs.is_satisfied());
+    assert_eq!(cs.which_is_unsatisfied(), Some("y in range/weighted sum"));
+}
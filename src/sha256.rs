@@ -0,0 +1,143 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+extern crate bellman;
+extern crate pairing;
+extern crate rand;
+extern crate sapling_crypto;
+extern crate sha2;
+
+// For randomness (during paramgen and proof generation)
+use self::rand::{thread_rng, Rng};
+
+// A host SHA-256 for computing the expected digest out of circuit.
+use self::sha2::{Sha256, Digest};
+
+// Bring in some tools for using pairing-friendly curves
+use self::pairing::{
+    Engine,
+    Field,
+    PrimeField
+};
+
+// We're going to use the BLS12-381 pairing-friendly elliptic curve.
+use self::pairing::bls12_381::{
+    Bls12,
+    Fr
+};
+
+// We'll use these interfaces to construct our circuit.
+use self::bellman::{
+    Circuit,
+    ConstraintSystem,
+    SynthesisError
+};
+
+// The boolean / sha256 / multipack gadgets do the heavy lifting.
+use self::sapling_crypto::circuit::boolean::{AllocatedBit, Boolean};
+use self::sapling_crypto::circuit::sha256::sha256;
+use self::sapling_crypto::circuit::multipack;
+
+// We're going to use the Groth16 proving system.
+use self::bellman::groth16::{
+    Proof,
+    generate_random_parameters,
+    prepare_verifying_key,
+    create_random_proof,
+    verify_proof,
+};
+
+// Length of the preimage, in bytes.
+const PREIMAGE_LEN: usize = 80;
+
+// Proving that I know a preimage whose SHA-256d digest equals a public value.
+// The digest is exposed by packing its 256 bits into field elements rather
+// than allocating 256 separate public inputs.
+pub struct Sha256Preimage {
+    pub preimage: Option<Vec<u8>>,
+}
+
+impl <E: Engine> Circuit<E> for Sha256Preimage {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        // Witness each preimage bit, big-endian within each byte to match the
+        // bit order the sha256 gadget consumes and emits.
+        let bit_values = match self.preimage {
+            Some(ref preimage) => {
+                assert_eq!(preimage.len(), PREIMAGE_LEN);
+                preimage.iter()
+                    .flat_map(|byte| (0..8).rev().map(move |i| Some((byte >> i) & 1u8 == 1u8)))
+                    .collect::<Vec<_>>()
+            },
+            None => vec![None; PREIMAGE_LEN * 8],
+        };
+
+        let preimage_bits = bit_values.into_iter()
+            .enumerate()
+            .map(|(i, b)| {
+                AllocatedBit::alloc(cs.namespace(|| format!("preimage bit {}", i)), b)
+                    .map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        // SHA-256d is just sha256 chained twice. The gadget consumes and emits
+        // its bit vector in one consistent order, so the first digest feeds
+        // straight into the second round with no reordering.
+        let first = sha256(
+            cs.namespace(|| "SHA-256(preimage)"),
+            &preimage_bits
+        )?;
+
+        let digest = sha256(
+            cs.namespace(|| "SHA-256(SHA-256(preimage))"),
+            &first
+        )?;
+
+        // Expose the 256-bit digest as a handful of packed public inputs.
+        multipack::pack_into_inputs(cs.namespace(|| "pack digest"), &digest)
+    }
+}
+
+// SHA-256d of the given bytes, computed with the host hasher.
+fn sha256d(data: &[u8]) -> Vec<u8> {
+    let first = Sha256::digest(data);
+    Sha256::digest(&first).to_vec()
+}
+
+#[test]
+fn test_sha256_proof(){
+    // This may not be cryptographically safe, use
+    // `OsRng` (for example) in production software.
+    let rng = &mut thread_rng();
+
+    println!("SETUP: Creating parameters...");
+
+    let params = {
+        let c = Sha256Preimage { preimage: None };
+        generate_random_parameters::<Bls12, _, _>(c, rng).unwrap()
+    };
+
+    let pvk = prepare_verifying_key(&params.vk);
+
+    // A fixed preimage whose digest we'll prove knowledge of.
+    let preimage = vec![0xabu8; PREIMAGE_LEN];
+
+    println!("Alice: Creating proof...");
+    let c = Sha256Preimage { preimage: Some(preimage.clone()) };
+    let proof = create_random_proof(c, &params, rng).unwrap();
+
+    // Compute the expected digest out of circuit and pack it exactly the way
+    // `pack_into_inputs` does inside the circuit.
+    let digest = sha256d(&preimage);
+    let digest_bits = multipack::bytes_to_bits(&digest);
+    let inputs = multipack::compute_multipacking::<Bls12>(&digest_bits);
+
+    println!("Bob: Verifying...");
+    assert!(verify_proof(
+        &pvk,
+        &proof,
+        &inputs
+    ).unwrap());
+}